@@ -0,0 +1,117 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use image::imageops::FilterType;
+use serde_json::Value;
+use tera::{Error, Function, Result};
+use url::Url;
+
+use crate::util;
+
+const PROCESSED_SUBDIR: &str = "processed";
+
+pub struct ImageContext {
+    pub content_dir: PathBuf,
+    pub static_dir: PathBuf,
+    pub output_dir: PathBuf,
+    pub base_url: Url,
+}
+
+impl ImageContext {
+    /// Find the source image in the static dir, falling back to the content dir, since authors
+    /// may keep images alongside either.
+    fn resolve(&self, input: &str) -> Result<PathBuf> {
+        [self.static_dir.join(input), self.content_dir.join(input)]
+            .into_iter()
+            .find(|p| p.exists())
+            .ok_or_else(|| Error::msg(format!("Image not found: {:?}", input)))
+    }
+}
+
+impl Function for ImageContext {
+    fn call(&self, args: &HashMap<String, Value>) -> Result<Value> {
+        let input = args.get("input").and_then(Value::as_str);
+        let width = args.get("width").and_then(Value::as_u64).map(|w| w as u32);
+        let height = args.get("height").and_then(Value::as_u64).map(|h| h as u32);
+        let op = args.get("op").and_then(Value::as_str).unwrap_or("fit");
+
+        match input {
+            Some(input) => {
+                let src = self.resolve(input)?;
+                let bytes = fs::read(&src).map_err(|e| Error::msg(e.to_string()))?;
+
+                // Cache processed variants by the hash of their source bytes and parameters, so
+                // repeated calls within a build, and `watch`'s incremental and `serve`'s in-memory
+                // rebuilds (which don't clean the target dir), don't re-decode and re-resize
+                // unchanged images. A full `build` cleans the target dir first and so processes
+                // every image again.
+                let mut hasher = DefaultHasher::new();
+                bytes.hash(&mut hasher);
+                width.hash(&mut hasher);
+                height.hash(&mut hasher);
+                op.hash(&mut hasher);
+                let ext = extension_of(&src);
+                let filename = format!("{:016x}.{}", hasher.finish(), ext);
+                let output_path = self.output_dir.join(PROCESSED_SUBDIR).join(&filename);
+
+                if !output_path.exists() {
+                    let img = image::load_from_memory(&bytes).map_err(|e| Error::msg(e.to_string()))?;
+                    let resized = resize(img, op, width, height);
+                    let mut out = Vec::new();
+                    resized
+                        .write_to(&mut std::io::Cursor::new(&mut out), format_of(&ext))
+                        .map_err(|e| Error::msg(e.to_string()))?;
+                    util::write_p(&output_path, out)?;
+                }
+
+                let mut image_url = self.base_url.clone();
+                let mut path = image_url
+                    .path_segments_mut()
+                    .map_err(|_| Error::msg("Invalid site URL"))?;
+                path.push(PROCESSED_SUBDIR);
+                path.push(&filename);
+                drop(path);
+                Ok(Value::String(image_url.path().to_string()))
+            }
+            None => Err(Error::msg("invalid args")),
+        }
+    }
+
+    fn is_safe(&self) -> bool {
+        true
+    }
+}
+
+fn extension_of(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("png")
+        .to_lowercase()
+}
+
+fn format_of(ext: &str) -> image::ImageFormat {
+    image::ImageFormat::from_extension(ext).unwrap_or(image::ImageFormat::Png)
+}
+
+fn resize(img: image::DynamicImage, op: &str, width: Option<u32>, height: Option<u32>) -> image::DynamicImage {
+    let (w, h) = (width.unwrap_or(img.width()), height.unwrap_or(img.height()));
+    match op {
+        // Stretch to exactly the given dimensions, ignoring aspect ratio.
+        "scale" => img.resize_exact(
+            width.unwrap_or(img.width()),
+            height.unwrap_or(img.height()),
+            FilterType::Lanczos3,
+        ),
+        // Crop to exactly the given dimensions, covering the box and cutting off the rest.
+        "crop" => img.resize_to_fill(
+            width.unwrap_or(img.width()),
+            height.unwrap_or(img.height()),
+            FilterType::Lanczos3,
+        ),
+        // Fit within the given dimensions, preserving aspect ratio.
+        _ => img.resize(w, h, FilterType::Lanczos3),
+    }
+}
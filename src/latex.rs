@@ -1,22 +1,127 @@
 use anyhow::{anyhow, Context, Result};
 use katex::Opts;
 use nom::branch::alt;
-use nom::bytes::complete::{tag, take_until};
+use nom::bytes::complete::{tag, tag_no_case, take_until, take_while1};
 use nom::character::complete::anychar;
 use nom::combinator::{eof, map, peek};
 use nom::multi::many_till;
-use nom::sequence::delimited;
+use nom::sequence::{delimited, pair};
 use nom::IResult;
+use nom_locate::LocatedSpan;
 use std::collections::HashMap;
 
+/// The input type threaded through every parser, tracking byte offset, line, and column as it's
+/// consumed so each `AST` node can carry the location of its opening delimiter.
+type Input<'a> = LocatedSpan<&'a str>;
+
+/// The location of an `AST` node's opening delimiter in the original document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub offset: usize,
+    pub line: u32,
+    pub column: usize,
+}
+
+impl<'a> From<&Input<'a>> for Span {
+    fn from(i: &Input<'a>) -> Self {
+        Span {
+            offset: i.location_offset(),
+            line: i.location_line(),
+            column: i.get_utf8_column(),
+        }
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, col {}", self.line, self.column)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum AST {
-    Literal(String),
-    InlineEq(String),
-    BlockEq(String),
+    Literal(String, Span),
+    InlineEq(String, Span),
+    BlockEq(String, Span),
+    /// An org-style `#+BEGIN_<name> ... #+END_<name>` block, rendered as the LaTeX environment of
+    /// the same name (e.g. `align`, `gather`, `pmatrix`).
+    Environment(String, String, Span),
 }
 
-pub fn render_latex(ast: Vec<AST>, macros: &HashMap<String, String>) -> Result<String> {
+/// Maps plain-text shorthand (e.g. `"in"`, `"infty"`) to the Unicode or LaTeX symbol it expands
+/// to. Expansion runs on equation source before it reaches KaTeX, alongside (but independently
+/// of) the `macros` table KaTeX itself applies.
+pub type SymbolTable = HashMap<String, String>;
+
+/// A sensible default `SymbolTable`, inspired by Typst's built-in math symbols: readable ASCII
+/// shorthand (`sum`, `in`, `arrow`, `NN`, `RR`) and the raw Unicode symbols they stand for (`∑`,
+/// `∈`, `ℝ`) both expand to the same LaTeX command, so authors can write either without
+/// memorizing KaTeX's command names.
+pub fn default_symbols() -> SymbolTable {
+    [
+        ("sum", "\\sum"),
+        ("in", "\\in"),
+        ("arrow", "\\to"),
+        ("NN", "\\mathbb{N}"),
+        ("RR", "\\mathbb{R}"),
+        ("∑", "\\sum"),
+        ("∈", "\\in"),
+        ("ℝ", "\\mathbb{R}"),
+    ]
+    .into_iter()
+    .map(|(token, expansion)| (token.to_string(), expansion.to_string()))
+    .collect()
+}
+
+/// Replace whole-word occurrences of `symbols`' keys in `s`, leaving word-internal substrings
+/// alone (so a shorthand for `"in"` doesn't clobber the `"in"` inside `"\sin"`). A lone
+/// non-alphanumeric character (e.g. a raw Unicode symbol like `∑`) is also checked against
+/// `symbols`, since it can never form part of a word run.
+fn expand_symbols(s: &str, symbols: &SymbolTable) -> String {
+    if symbols.is_empty() {
+        return s.to_string();
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut word_start: Option<usize> = None;
+
+    for (i, c) in s.char_indices() {
+        match (c.is_alphanumeric() || c == '_', word_start) {
+            (true, None) => word_start = Some(i),
+            (false, Some(start)) => {
+                expand_token(&mut out, &s[start..i], symbols);
+                expand_char(&mut out, c, symbols);
+                word_start = None;
+            }
+            (false, None) => expand_char(&mut out, c, symbols),
+            (true, Some(_)) => {}
+        }
+    }
+    if let Some(start) = word_start {
+        expand_token(&mut out, &s[start..], symbols);
+    }
+
+    out
+}
+
+fn expand_token(out: &mut String, token: &str, symbols: &SymbolTable) {
+    match symbols.get(token) {
+        Some(expansion) => out.push_str(expansion),
+        None => out.push_str(token),
+    }
+}
+
+/// As `expand_token`, but for a single character that can't be part of a word run.
+fn expand_char(out: &mut String, c: char, symbols: &SymbolTable) {
+    let mut buf = [0u8; 4];
+    expand_token(out, c.encode_utf8(&mut buf), symbols);
+}
+
+pub fn render_latex(
+    ast: Vec<AST>,
+    macros: &HashMap<String, String>,
+    symbols: &SymbolTable,
+) -> Result<String> {
     let block_opts = Opts::builder()
         .display_mode(true)
         .trust(true)
@@ -33,37 +138,248 @@ pub fn render_latex(ast: Vec<AST>, macros: &HashMap<String, String>) -> Result<S
 
     for item in ast {
         out += &match item {
-            AST::Literal(s) => s,
-            AST::InlineEq(s) => katex::render_with_opts(&s, &inline_opts)
-                .with_context(|| format!("Invalid LaTeX equation: {:?}", s))?,
-            AST::BlockEq(s) => katex::render_with_opts(&s, &block_opts)
-                .with_context(|| format!("Invalid LaTeX equation: {:?}", s))?,
+            AST::Literal(s, _) => s,
+            AST::InlineEq(s, span) => {
+                let s = expand_symbols(&s, symbols);
+                katex::render_with_opts(&s, &inline_opts)
+                    .with_context(|| format!("Invalid LaTeX equation at {}: {:?}", span, s))?
+            }
+            AST::BlockEq(s, span) => {
+                let s = expand_symbols(&s, symbols);
+                katex::render_with_opts(&s, &block_opts)
+                    .with_context(|| format!("Invalid LaTeX equation at {}: {:?}", span, s))?
+            }
+            AST::Environment(name, body, span) => {
+                let body = expand_symbols(&body, symbols);
+                let source = format!("\\begin{{{name}}}{body}\\end{{{name}}}");
+                katex::render_with_opts(&source, &block_opts)
+                    .with_context(|| format!("Invalid LaTeX equation at {}: {:?}", span, source))?
+            }
         }
     }
 
     Ok(out)
 }
 
+/// A traversal hook for an `AST`, with a default no-op implementation for every node kind so
+/// implementors only need to override the ones they care about.
+pub trait Visitor<'a> {
+    fn visit_literal(&mut self, _s: &'a str, _span: Span) {}
+    fn visit_inline_eq(&mut self, _s: &'a str, _span: Span) {}
+    fn visit_block_eq(&mut self, _s: &'a str, _span: Span) {}
+    fn visit_environment(&mut self, _name: &'a str, _body: &'a str, _span: Span) {}
+    /// Called once after every node has been visited.
+    fn finish(&mut self) {}
+}
+
+/// Walk `ast` in order, dispatching each node to the matching `Visitor` hook.
+pub fn visit<'a>(ast: &'a [AST], visitor: &mut impl Visitor<'a>) {
+    for item in ast {
+        match item {
+            AST::Literal(s, span) => visitor.visit_literal(s, *span),
+            AST::InlineEq(s, span) => visitor.visit_inline_eq(s, *span),
+            AST::BlockEq(s, span) => visitor.visit_block_eq(s, *span),
+            AST::Environment(name, body, span) => visitor.visit_environment(name, body, *span),
+        }
+    }
+    visitor.finish();
+}
+
+/// Collects the source and span of every equation (inline, block, or environment) in an `AST`.
+#[derive(Debug, Default)]
+pub struct EquationCollector<'a> {
+    equations: Vec<(Span, &'a str)>,
+}
+
+impl<'a> EquationCollector<'a> {
+    pub fn into_equations(self) -> Vec<(Span, &'a str)> {
+        self.equations
+    }
+}
+
+impl<'a> Visitor<'a> for EquationCollector<'a> {
+    fn visit_inline_eq(&mut self, s: &'a str, span: Span) {
+        self.equations.push((span, s));
+    }
+
+    fn visit_block_eq(&mut self, s: &'a str, span: Span) {
+        self.equations.push((span, s));
+    }
+
+    fn visit_environment(&mut self, _name: &'a str, body: &'a str, span: Span) {
+        self.equations.push((span, body));
+    }
+}
+
+/// Reconstruct delimited source for an `AST`, re-inserting each node's delimiters. Environments
+/// are printed with upper-case `#+BEGIN_`/`#+END_` keywords and without any arguments that
+/// followed the name on the `#+BEGIN_` line, since the `AST` doesn't keep them. For any string `s`
+/// that `parse_latex` accepts and whose environments have no such arguments,
+/// `parse_latex(&print(parse_latex(s)?)) == parse_latex(s)`; where arguments were dropped, the
+/// spans of the nodes that follow them differ.
+pub fn print(ast: &[AST]) -> String {
+    let mut out = String::new();
+    for item in ast {
+        match item {
+            AST::Literal(s, _) => out.push_str(s),
+            AST::InlineEq(s, _) => {
+                out.push_str(INLINE_START_DELIM);
+                out.push_str(s);
+                out.push_str(INLINE_END_DELIM);
+            }
+            AST::BlockEq(s, _) => {
+                out.push_str(BLOCK_START_DELIM);
+                out.push_str(s);
+                out.push_str(BLOCK_END_DELIM);
+            }
+            AST::Environment(name, body, _) => {
+                out.push_str(ENV_BEGIN_PREFIX);
+                out.push_str(name);
+                out.push('\n');
+                out.push_str(body);
+                out.push_str(ENV_END_PREFIX);
+                out.push_str(name);
+            }
+        }
+    }
+    out
+}
+
+/// A single equation that KaTeX rejected, recorded by `render_all` instead of aborting the whole
+/// render.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EquationError {
+    pub span: Span,
+    pub source: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for EquationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid LaTeX equation at {}: {:?}: {}",
+            self.span, self.source, self.message
+        )
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Parse and render `input`, recovering from individual equation failures rather than bailing on
+/// the first one: every equation is rendered independently, and any KaTeX rejects is replaced
+/// with a `<span class="katex-error">` placeholder and recorded in the returned
+/// `Vec<EquationError>`. Malformed delimiters (e.g. an unterminated `$$`) still fail the parse, as
+/// they do for `parse_latex`.
+pub fn render_all(
+    input: &str,
+    macros: &HashMap<String, String>,
+    symbols: &SymbolTable,
+) -> Result<(String, Vec<EquationError>)> {
+    let ast = parse_latex(input)?;
+    let block_opts = Opts::builder()
+        .display_mode(true)
+        .trust(true)
+        .macros(macros.clone())
+        .build()
+        .unwrap();
+    let inline_opts = Opts::builder()
+        .display_mode(false)
+        .trust(true)
+        .macros(macros.clone())
+        .build()
+        .unwrap();
+    let mut out = String::with_capacity(ast.len() * 100);
+    let mut errors = Vec::new();
+
+    for item in ast {
+        out += &match item {
+            AST::Literal(s, _) => s,
+            AST::InlineEq(s, span) => render_or_placeholder(
+                expand_symbols(&s, symbols),
+                span,
+                &inline_opts,
+                &mut errors,
+            ),
+            AST::BlockEq(s, span) => render_or_placeholder(
+                expand_symbols(&s, symbols),
+                span,
+                &block_opts,
+                &mut errors,
+            ),
+            AST::Environment(name, body, span) => {
+                let body = expand_symbols(&body, symbols);
+                let source = format!("\\begin{{{name}}}{body}\\end{{{name}}}");
+                render_or_placeholder(source, span, &block_opts, &mut errors)
+            }
+        }
+    }
+
+    Ok((out, errors))
+}
+
+/// Render a single equation, falling back to an inline error placeholder (and recording an
+/// `EquationError`) if KaTeX rejects it.
+fn render_or_placeholder(
+    source: String,
+    span: Span,
+    opts: &Opts,
+    errors: &mut Vec<EquationError>,
+) -> String {
+    match katex::render_with_opts(&source, opts) {
+        Ok(html) => html,
+        Err(e) => {
+            let message = e.to_string();
+            let placeholder = format!(
+                r#"<span class="katex-error" title="{}">{}</span>"#,
+                escape_html(&message),
+                escape_html(&source)
+            );
+            errors.push(EquationError {
+                span,
+                source,
+                message,
+            });
+            placeholder
+        }
+    }
+}
+
 pub fn parse_latex(i: &str) -> Result<Vec<AST>> {
     map(
         many_till(
-            alt((parse_block_equation, parse_inline_equation, parse_text)),
+            alt((
+                parse_environment,
+                parse_block_equation,
+                parse_inline_equation,
+                parse_text,
+            )),
             eof,
         ),
         |(ast, _)| ast,
-    )(i)
+    )(Input::new(i))
     .map(|(_, ast)| ast)
-    .map_err(|_| anyhow!("Invalid LaTeX delimiters"))
+    .map_err(|e| match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => {
+            anyhow!("Invalid LaTeX delimiters at {}", Span::from(&e.input))
+        }
+        nom::Err::Incomplete(_) => anyhow!("Invalid LaTeX delimiters"),
+    })
 }
 
 const INLINE_START_DELIM: &str = r#"\\("#;
 const INLINE_END_DELIM: &str = r#"\\)"#;
 
-fn parse_inline_equation(i: &str) -> IResult<&str, AST> {
+fn parse_inline_equation(i: Input) -> IResult<Input, AST> {
+    let span = Span::from(&i);
     delimited(
         tag(INLINE_START_DELIM),
-        map(take_until(INLINE_END_DELIM), |s: &str| {
-            AST::InlineEq(s.to_string())
+        map(take_until(INLINE_END_DELIM), move |s: Input| {
+            AST::InlineEq(s.fragment().to_string(), span)
         }),
         tag(INLINE_END_DELIM),
     )(i)
@@ -72,65 +388,144 @@ fn parse_inline_equation(i: &str) -> IResult<&str, AST> {
 const BLOCK_START_DELIM: &str = r#"$$"#;
 const BLOCK_END_DELIM: &str = r#"$$"#;
 
-fn parse_block_equation(i: &str) -> IResult<&str, AST> {
+fn parse_block_equation(i: Input) -> IResult<Input, AST> {
+    let span = Span::from(&i);
     delimited(
         tag(BLOCK_START_DELIM),
-        map(take_until(BLOCK_END_DELIM), |s: &str| {
-            AST::BlockEq(s.to_string())
+        map(take_until(BLOCK_END_DELIM), move |s: Input| {
+            AST::BlockEq(s.fragment().to_string(), span)
         }),
         tag(BLOCK_END_DELIM),
     )(i)
 }
 
-fn parse_text(i: &str) -> IResult<&str, AST> {
+fn parse_text(i: Input) -> IResult<Input, AST> {
+    let span = Span::from(&i);
     map(
         many_till(
             anychar,
-            peek(alt((eof, tag(BLOCK_START_DELIM), tag(INLINE_START_DELIM)))),
+            peek(alt((
+                eof,
+                tag(BLOCK_START_DELIM),
+                tag(INLINE_START_DELIM),
+                tag_no_case(ENV_BEGIN_PREFIX),
+            ))),
         ),
-        |(a, _)| AST::Literal(a.into_iter().collect()),
+        move |(a, _)| AST::Literal(a.into_iter().collect(), span),
     )(i)
 }
 
+const ENV_BEGIN_PREFIX: &str = "#+BEGIN_";
+const ENV_END_PREFIX: &str = "#+END_";
+
+fn parse_environment(i: Input) -> IResult<Input, AST> {
+    let span = Span::from(&i);
+    let (i, _) = tag_no_case(ENV_BEGIN_PREFIX)(i)?;
+    let (i, name) = take_while1(|c: char| !c.is_whitespace())(i)?;
+    let name = name.fragment().to_string();
+    // The rest of the BEGIN line is free-form arguments (e.g. `#+BEGIN_align ref:eq1`); they're
+    // not part of the environment name and aren't passed through to KaTeX.
+    let (i, _) = take_until("\n")(i)?;
+    let (i, _) = tag("\n")(i)?;
+    let (i, (body, _)) = many_till(
+        anychar,
+        peek(pair(tag_no_case(ENV_END_PREFIX), tag(name.as_str()))),
+    )(i)?;
+    let (i, _) = tag_no_case(ENV_END_PREFIX)(i)?;
+    let (i, _) = tag(name.as_str())(i)?;
+    Ok((i, AST::Environment(name, body.into_iter().collect(), span)))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    fn span(offset: usize, line: u32, column: usize) -> Span {
+        Span {
+            offset,
+            line,
+            column,
+        }
+    }
+
     #[test]
     fn regular_text() {
         assert_eq!(
-            Ok(("$$", AST::Literal("one two three ".to_string()))),
-            parse_text("one two three $$")
+            Ok(("$$", AST::Literal("one two three ".to_string(), span(0, 1, 1)))),
+            parse_text(Input::new("one two three $$")).map(|(i, ast)| (*i.fragment(), ast))
         );
 
         assert_eq!(
-            Ok((r#"\\("#, AST::Literal("one two three ".to_string()))),
-            parse_text(r#"one two three \\("#)
+            Ok((r#"\\("#, AST::Literal("one two three ".to_string(), span(0, 1, 1)))),
+            parse_text(Input::new(r#"one two three \\("#)).map(|(i, ast)| (*i.fragment(), ast))
         );
 
         assert_eq!(
-            Ok(("", AST::Literal("one two three".to_string()))),
-            parse_text("one two three")
+            Ok(("", AST::Literal("one two three".to_string(), span(0, 1, 1)))),
+            parse_text(Input::new("one two three")).map(|(i, ast)| (*i.fragment(), ast))
         );
 
-        assert_eq!(Ok(("", AST::Literal("".to_string()))), parse_text(""));
+        assert_eq!(
+            Ok(("", AST::Literal("".to_string(), span(0, 1, 1)))),
+            parse_text(Input::new("")).map(|(i, ast)| (*i.fragment(), ast))
+        );
     }
 
     #[test]
     fn inline_equation() {
         assert_eq!(
-            Ok(("", AST::InlineEq("one two three".to_string()))),
-            parse_inline_equation(r#"\\(one two three\\)"#)
+            Ok(("", AST::InlineEq("one two three".to_string(), span(0, 1, 1)))),
+            parse_inline_equation(Input::new(r#"\\(one two three\\)"#))
+                .map(|(i, ast)| (*i.fragment(), ast))
         );
 
-        assert!(parse_inline_equation("goof troop").is_err());
+        assert!(parse_inline_equation(Input::new("goof troop")).is_err());
     }
 
     #[test]
     fn block_equation() {
         assert_eq!(
-            Ok(("", AST::BlockEq("one two three".to_string()))),
-            parse_block_equation("$$one two three$$")
+            Ok(("", AST::BlockEq("one two three".to_string(), span(0, 1, 1)))),
+            parse_block_equation(Input::new("$$one two three$$"))
+                .map(|(i, ast)| (*i.fragment(), ast))
+        );
+    }
+
+    #[test]
+    fn named_environment() {
+        assert_eq!(
+            Ok((
+                "",
+                AST::Environment("align".to_string(), "a &= b \\\\\nc &= d\n".to_string(), span(0, 1, 1))
+            )),
+            parse_environment(Input::new("#+BEGIN_align\na &= b \\\\\nc &= d\n#+END_align"))
+                .map(|(i, ast)| (*i.fragment(), ast))
+        );
+
+        assert!(parse_environment(Input::new("#+BEGIN_align\na &= b\n#+END_gather")).is_err());
+    }
+
+    #[test]
+    fn named_environment_keyword_is_case_insensitive() {
+        assert_eq!(
+            Ok((
+                "",
+                AST::Environment("align".to_string(), "a &= b\n".to_string(), span(0, 1, 1))
+            )),
+            parse_environment(Input::new("#+begin_align\na &= b\n#+End_align"))
+                .map(|(i, ast)| (*i.fragment(), ast))
+        );
+    }
+
+    #[test]
+    fn named_environment_ignores_trailing_arguments() {
+        assert_eq!(
+            Ok((
+                "",
+                AST::Environment("align".to_string(), "a &= b\n".to_string(), span(0, 1, 1))
+            )),
+            parse_environment(Input::new("#+BEGIN_align ref:eq1\na &= b\n#+END_align"))
+                .map(|(i, ast)| (*i.fragment(), ast))
         );
     }
 
@@ -139,40 +534,98 @@ mod test {
         assert!(parse_latex(r#"one two $$ three"#).is_err());
 
         assert_eq!(
-            vec![AST::Literal("one two three".to_string())],
+            vec![AST::Literal("one two three".to_string(), span(0, 1, 1))],
             parse_latex(r#"one two three"#).unwrap()
         );
 
         assert_eq!(
             vec![
-                AST::Literal("one two ".to_string()),
-                AST::BlockEq("N=1".to_string()),
-                AST::Literal(" three".to_string()),
+                AST::Literal("one two ".to_string(), span(0, 1, 1)),
+                AST::BlockEq("N=1".to_string(), span(8, 1, 9)),
+                AST::Literal(" three".to_string(), span(15, 1, 16)),
             ],
             parse_latex(r#"one two $$N=1$$ three"#).unwrap()
         );
 
         assert_eq!(
             vec![
-                AST::Literal("one two ".to_string()),
-                AST::InlineEq("N=1".to_string()),
-                AST::Literal(" three".to_string()),
+                AST::Literal("one two ".to_string(), span(0, 1, 1)),
+                AST::InlineEq("N=1".to_string(), span(8, 1, 9)),
+                AST::Literal(" three".to_string(), span(17, 1, 18)),
             ],
             parse_latex(r#"one two \\(N=1\\) three"#).unwrap()
         );
     }
 
+    #[test]
+    fn render_all_recovers_from_bad_equations() {
+        let (html, errors) = render_all(
+            r#"one $$\frac{1}{$$ two \\(N=1\\) three"#,
+            &HashMap::default(),
+            &SymbolTable::default(),
+        )
+        .expect("error parsing LaTeX");
+
+        assert_eq!(1, errors.len());
+        assert_eq!(span(4, 1, 5), errors[0].span);
+        assert_eq!(r#"\frac{1}{"#, errors[0].source);
+        assert!(html.contains(r#"<span class="katex-error""#));
+        assert!(html.contains("three"));
+    }
+
+    #[test]
+    fn expands_whole_word_symbols_only() {
+        let symbols: SymbolTable = [("in".to_string(), "\\in".to_string())].into_iter().collect();
+
+        assert_eq!("x \\in S", expand_symbols("x in S", &symbols));
+        assert_eq!("\\sin(x)", expand_symbols("\\sin(x)", &symbols));
+    }
+
+    #[test]
+    fn expands_raw_unicode_symbols() {
+        let symbols = default_symbols();
+
+        assert_eq!("x \\in \\mathbb{R}", expand_symbols("x ∈ RR", &symbols));
+        assert_eq!("\\sum_i x_i", expand_symbols("∑_i x_i", &symbols));
+    }
+
+    #[test]
+    fn print_round_trips() {
+        let source = r#"one two $$N=1$$ three \\(M=2\\) four #+BEGIN_align
+a &= b
+#+END_align five"#;
+
+        let ast = parse_latex(source).unwrap();
+
+        assert_eq!(source, print(&ast));
+        assert_eq!(ast, parse_latex(&print(&ast)).unwrap());
+    }
+
+    #[test]
+    fn visitor_collects_equations() {
+        let ast = parse_latex(r#"one $$N=1$$ two \\(M=2\\) three"#).unwrap();
+
+        let mut collector = EquationCollector::default();
+        visit(&ast, &mut collector);
+
+        assert_eq!(
+            vec![(span(4, 1, 5), "N=1"), (span(16, 1, 17), "M=2")],
+            collector.into_equations()
+        );
+    }
+
     #[test]
     fn html() {
         let html = render_latex(
             vec![
-                AST::Literal("one ".to_string()),
-                AST::InlineEq("N".to_string()),
-                AST::Literal(" ".to_string()),
-                AST::BlockEq("\\sigma".to_string()),
-                AST::Literal(" two".to_string()),
+                AST::Literal("one ".to_string(), span(0, 1, 1)),
+                AST::InlineEq("N".to_string(), span(4, 1, 5)),
+                AST::Literal(" ".to_string(), span(9, 1, 10)),
+                AST::BlockEq("\\sigma".to_string(), span(10, 1, 11)),
+                AST::Literal(" two".to_string(), span(20, 1, 21)),
             ],
             &HashMap::default(),
+            &SymbolTable::default(),
         )
         .expect("error rendering LaTeX");
 
@@ -182,3 +635,30 @@ mod test {
         )
     }
 }
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn arb_piece() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "[a-zA-Z0-9 ]{1,8}",
+            "[a-zA-Z0-9 ]{1,8}".prop_map(|s| format!(r#"\\({s}\\)"#)),
+            "[a-zA-Z0-9 ]{1,8}".prop_map(|s| format!("$${s}$$")),
+            ("[a-zA-Z]{1,6}", "[a-zA-Z0-9 ]{1,8}")
+                .prop_map(|(name, body)| format!("#+BEGIN_{name}\n{body}\n#+END_{name}")),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips(pieces in proptest::collection::vec(arb_piece(), 1..6)) {
+            let source = pieces.concat();
+            let ast = parse_latex(&source).unwrap();
+
+            prop_assert_eq!(print(&ast), source);
+            prop_assert_eq!(parse_latex(&print(&ast)).unwrap(), ast);
+        }
+    }
+}
@@ -1,19 +1,38 @@
 use std::path::PathBuf;
 
 use anyhow::Result;
-use clap::{Parser, ValueHint};
+use clap::{Parser, ValueEnum, ValueHint};
 
+mod imageproc;
+mod latex;
+mod sass;
 mod site;
+mod util;
 
 fn main() -> Result<()> {
     let opts: Opts = Opts::parse();
-    if opts.watch {
+    if opts.serve {
+        site::serve(&opts.dir, opts.drafts, opts.port)
+    } else if opts.watch {
         site::watch(&opts.dir, opts.drafts)
     } else {
-        site::build(&opts.dir, opts.drafts)
+        match opts.format {
+            Format::Html => site::build(&opts.dir, opts.drafts),
+            Format::Pdf => site::build_pdf(&opts.dir, opts.drafts),
+        }
     }
 }
 
+/// The output format to render the site in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Render every page as HTML, as `build` always has.
+    Html,
+    /// Concatenate dated pages into a single LaTeX document (and, if `xelatex` is on the path,
+    /// a compiled PDF) instead of a static HTML site.
+    Pdf,
+}
+
 /// Build a dang website, I guess.
 #[deny(missing_docs)]
 #[derive(Debug, Parser)]
@@ -30,4 +49,16 @@ struct Opts {
     /// Watch for changed files and rebuild.
     #[clap(long)]
     watch: bool,
+
+    /// Serve the site from memory with live reload, rebuilding as files change.
+    #[clap(long)]
+    serve: bool,
+
+    /// The port to serve the site on.
+    #[clap(long, default_value = "8000")]
+    port: u16,
+
+    /// The output format to render.
+    #[clap(long, value_enum, default_value = "html")]
+    format: Format,
 }
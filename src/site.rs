@@ -2,9 +2,11 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fs;
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{BufWriter, Read};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc;
+use std::process::Command;
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::thread;
 use std::time::Duration;
 
 use anyhow::{anyhow, bail, Context, Result};
@@ -14,19 +16,24 @@ use ctor::ctor;
 use globwalk::{FileType, GlobWalkerBuilder};
 use grass::OutputStyle;
 use gray_matter::{engine, Matter};
-use katex::Opts;
 use lazy_static::lazy_static;
 use notify::{DebouncedEvent, RecursiveMode, Watcher};
-use pulldown_cmark::{html, CodeBlockKind, Event, Options, Parser, Tag};
+use pulldown_cmark::{html, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag};
 use serde::{Deserialize, Serialize};
 use syntect::highlighting::ThemeSet;
-use syntect::html::highlighted_html_for_string;
+use syntect::html::{css_for_theme_with_class_style, highlighted_html_for_string, ClassStyle, ClassedHTMLGenerator};
 use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
 use tera::{Context as TeraContext, Tera};
+use tiny_http::{Header, Response, Server};
 use tracing::instrument;
 use url::Url;
 use walkdir::{DirEntry, WalkDir};
 
+use crate::imageproc::ImageContext;
+use crate::latex;
+use crate::sass::SassContext;
+
 #[instrument]
 pub fn watch<P: AsRef<Path> + Debug>(dir: P, drafts: bool) -> Result<()> {
     // Convert the path to canonical, if possible.
@@ -36,14 +43,331 @@ pub fn watch<P: AsRef<Path> + Debug>(dir: P, drafts: bool) -> Result<()> {
         .with_context(|| format!("Failed to find site directory: {:?}", dir))?;
     let target_dir = dir.join(TARGET_SUBDIR);
 
-    // Create a channel pair for events and send a first event to trigger the initial build.
-    let (tx, rx) = mpsc::channel();
-    tx.send(DebouncedEvent::Write(dir.clone()))?;
+    // Run the initial full build, then seed the incremental rebuild cache so later content
+    // changes don't have to re-read and re-parse every page.
+    build(&dir, drafts)?;
+    let mut config = load_config(&dir)?;
+    let mut pages = load_and_render_pages(&dir, &config, drafts)?;
 
     // Watch the site directory for changes.
+    let (tx, rx) = mpsc::channel();
     let mut watcher = notify::watcher(tx, Duration::from_secs(1))?;
     watcher.watch(&dir, RecursiveMode::Recursive)?;
 
+    loop {
+        match rx.recv() {
+            Ok(event) => match event {
+                DebouncedEvent::Create(path)
+                | DebouncedEvent::Chmod(path)
+                | DebouncedEvent::Write(path)
+                | DebouncedEvent::Remove(path) => {
+                    process_change(&dir, &target_dir, &mut config, &mut pages, &path, drafts)?
+                }
+                DebouncedEvent::Rename(from, to) => {
+                    // Treat a rename as a deletion of the old path plus a creation of the new one;
+                    // otherwise only `from` (which `rebuild_page` sees as missing) is ever rebuilt
+                    // and the renamed file never appears until some unrelated `RebuildKind::Full`.
+                    process_change(&dir, &target_dir, &mut config, &mut pages, &from, drafts)?;
+                    process_change(&dir, &target_dir, &mut config, &mut pages, &to, drafts)?;
+                }
+                _ => {}
+            },
+            Err(e) => bail!(e),
+        }
+    }
+}
+
+/// Rebuild whatever `path` requires, ignoring files in the target dir and editor temp files.
+#[instrument(skip(config, pages))]
+fn process_change(
+    dir: &Path,
+    target_dir: &Path,
+    config: &mut SiteConfig,
+    pages: &mut Vec<Page>,
+    path: &Path,
+    drafts: bool,
+) -> Result<()> {
+    if path
+        .extension()
+        .map(|s| s.to_string_lossy().ends_with('~'))
+        .unwrap_or(false)
+        || path.starts_with(target_dir)
+    {
+        return Ok(());
+    }
+
+    tracing::info!(target:"rebuild", changed=?path);
+    match classify_change(dir, path) {
+        RebuildKind::Content(path) => rebuild_page(dir, target_dir, config, pages, &path, drafts),
+        RebuildKind::Sass => render_sass(dir, target_dir, &config.sass),
+        RebuildKind::Static(path) => rebuild_static_file(dir, target_dir, &path),
+        RebuildKind::Full => {
+            // Config and templates are shared by every page, so there's no cheaper path than a
+            // full rebuild.
+            build(dir, drafts)?;
+            *config = load_config(dir)?;
+            *pages = load_and_render_pages(dir, config, drafts)?;
+            Ok(())
+        }
+    }
+}
+
+/// Which part of the build pipeline a changed path requires re-running.
+#[derive(Debug)]
+enum RebuildKind {
+    /// A single content file changed; re-render it plus the feed and taxonomy pages.
+    Content(PathBuf),
+    /// A SASS partial or entry point changed; redo `render_sass`.
+    Sass,
+    /// A single static asset changed; re-copy just that file.
+    Static(PathBuf),
+    /// Config or templates changed, and both are shared by every page; redo everything.
+    Full,
+}
+
+#[instrument]
+fn classify_change(dir: &Path, path: &Path) -> RebuildKind {
+    if path.starts_with(dir.join(SASS_SUBDIR)) {
+        RebuildKind::Sass
+    } else if path.starts_with(dir.join(STATIC_SUBDIR)) {
+        RebuildKind::Static(path.to_path_buf())
+    } else if path.starts_with(dir.join(CONTENT_SUBDIR))
+        && path.extension().map(|e| e == "md").unwrap_or(false)
+    {
+        RebuildKind::Content(path.to_path_buf())
+    } else {
+        RebuildKind::Full
+    }
+}
+
+/// Load, filter, and render every content page, for seeding or refreshing the `watch` cache.
+#[instrument(skip(config))]
+fn load_and_render_pages(dir: &Path, config: &SiteConfig, drafts: bool) -> Result<Vec<Page>> {
+    let mut pages = load_pages(&dir.join(CONTENT_SUBDIR))?
+        .into_iter()
+        .filter(|p| drafts || !p.draft)
+        .collect::<Vec<Page>>();
+    render_markdown(&mut pages, &config.theme)?;
+    Ok(pages)
+}
+
+/// Re-parse and re-render a single content file, patch it into the cached `pages`, and redo just
+/// the HTML for that page, the feed, and the taxonomy pages (which aggregate every page).
+#[instrument(skip(config, pages))]
+fn rebuild_page(
+    dir: &Path,
+    target_dir: &Path,
+    config: &SiteConfig,
+    pages: &mut Vec<Page>,
+    path: &Path,
+    drafts: bool,
+) -> Result<()> {
+    let content_dir = dir.join(CONTENT_SUBDIR);
+
+    let mut page_name = path.strip_prefix(&content_dir).unwrap().to_path_buf();
+    page_name.set_extension("");
+    let page_name = page_name.to_string_lossy().to_string();
+
+    // The file may have been removed, or may now be a draft; either way, drop it from the cache,
+    // remove its previously rendered output, and leave it out of the rendered site.
+    if !path.exists() {
+        pages.retain(|p| p.name != page_name);
+        remove_page_output(target_dir, &page_name)?;
+        render_feed(&config.title, target_dir, config.base_url.as_str(), pages)?;
+        return render_taxonomies(dir, target_dir, config, pages);
+    }
+
+    let matter = Matter::<engine::TOML>::new();
+    let s =
+        fs::read_to_string(path).with_context(|| format!("Failed to read file {:?}", path))?;
+    let parsed = matter
+        .parse_with_struct::<Page>(&s)
+        .ok_or_else(|| anyhow!("Invalid front matter in {:?}", path))?;
+
+    let mut page = parsed.data;
+    page.content = parsed.content;
+    page.excerpt = parsed.excerpt;
+    page.name = page_name.clone();
+
+    if page.draft && !drafts {
+        pages.retain(|p| p.name != page_name);
+        remove_page_output(target_dir, &page_name)?;
+        render_feed(&config.title, target_dir, config.base_url.as_str(), pages)?;
+        return render_taxonomies(dir, target_dir, config, pages);
+    }
+
+    render_markdown(std::slice::from_mut(&mut page), &config.theme)?;
+
+    match pages.iter_mut().find(|p| p.name == page_name) {
+        Some(existing) => *existing = page,
+        None => pages.push(page),
+    }
+
+    let templates = build_templates(dir, target_dir, config)?;
+    let page = pages.iter().find(|p| p.name == page_name).unwrap();
+    render_page_html(&templates, target_dir, page, pages)?;
+    render_feed(&config.title, target_dir, config.base_url.as_str(), pages)?;
+    render_taxonomies(dir, target_dir, config, pages)
+}
+
+/// Remove a page's previously rendered output, mirroring the layout `render_page_html` writes:
+/// the index page is a bare file at the target root, every other page is a directory.
+fn remove_page_output(target_dir: &Path, page_name: &str) -> Result<()> {
+    if page_name == "index" {
+        let path = target_dir.join(INDEX_FILENAME);
+        if path.exists() {
+            fs::remove_file(&path).with_context(|| format!("Error removing {:?}", &path))?;
+        }
+    } else {
+        let path = target_dir.join(page_name);
+        if path.exists() {
+            fs::remove_dir_all(&path).with_context(|| format!("Error removing {:?}", &path))?;
+        }
+    }
+    Ok(())
+}
+
+/// Re-copy (or, if deleted, remove) a single static asset without rescanning the whole directory.
+#[instrument]
+fn rebuild_static_file(dir: &Path, target_dir: &Path, path: &Path) -> Result<()> {
+    let static_dir = dir.join(STATIC_SUBDIR);
+    let rel = path
+        .strip_prefix(&static_dir)
+        .with_context(|| format!("Error stripping asset prefix from {:?}", path))?;
+    let dst = target_dir.join(rel);
+
+    if path.exists() {
+        if let Some(parent) = dst.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Error creating directory: {:?}", parent))?;
+        }
+        fs::copy(path, &dst)
+            .with_context(|| format!("Error copying asset {:?} to {:?}", path, &dst))?;
+    } else {
+        let _ = fs::remove_file(&dst);
+    }
+
+    Ok(())
+}
+
+/// A page or asset held in memory, ready to be served without a disk round trip.
+struct MemoryFile {
+    content: Vec<u8>,
+    content_type: &'static str,
+}
+
+/// The set of rendered output, keyed by URL path (e.g. `/`, `/about/`, `/css/site.css`).
+type LiveSite = HashMap<String, MemoryFile>;
+
+/// The script injected into served HTML pages to open a live-reload connection and refresh the
+/// page when the dev server broadcasts a rebuild.
+const LIVE_RELOAD_SCRIPT: &str = r#"<script>
+(function() {
+  var source = new EventSource("/__bakery/reload");
+  source.onmessage = function() { location.reload(); };
+})();
+</script>
+</body>"#;
+
+const LIVE_RELOAD_PATH: &str = "/__bakery/reload";
+
+#[instrument]
+pub fn serve<P: AsRef<Path> + Debug>(dir: P, drafts: bool, port: u16) -> Result<()> {
+    // Convert the path to canonical, if possible.
+    let dir = dir
+        .as_ref()
+        .canonicalize()
+        .with_context(|| format!("Failed to find site directory: {:?}", dir))?;
+
+    let site = Arc::new(RwLock::new(render_to_memory(&dir, drafts)?));
+    let clients: Arc<Mutex<Vec<mpsc::Sender<()>>>> = Arc::new(Mutex::new(Vec::new()));
+
+    // Watch the site directory for changes and rebuild in memory, broadcasting a reload message
+    // to every connected browser when a rebuild finishes.
+    {
+        let dir = dir.clone();
+        let site = Arc::clone(&site);
+        let clients = Arc::clone(&clients);
+        thread::spawn(move || {
+            if let Err(e) = watch_and_rebuild(&dir, drafts, &site, &clients) {
+                tracing::error!(error=?e, "live-reload watcher died");
+            }
+        });
+    }
+
+    let server =
+        Server::http(("127.0.0.1", port)).map_err(|e| anyhow!("Unable to bind server: {}", e))?;
+    tracing::info!(port, "serving site");
+
+    for request in server.incoming_requests() {
+        let url = request.url().to_string();
+        if url == LIVE_RELOAD_PATH {
+            let clients = Arc::clone(&clients);
+            thread::spawn(move || handle_reload_conn(request, &clients));
+            continue;
+        }
+
+        let found = {
+            let site = site.read().unwrap();
+            site.get(&url)
+                .or_else(|| site.get(&with_trailing_index(&url)))
+                .map(|file| (file.content.clone(), file.content_type.to_string()))
+        };
+
+        match found.or_else(|| read_target_file(&dir, &url)) {
+            Some((content, content_type)) => {
+                let header = Header::from_bytes(&b"Content-Type"[..], content_type.as_bytes())
+                    .expect("valid header");
+                let response = Response::from_data(content).with_header(header);
+                let _ = request.respond(response);
+            }
+            None => {
+                let _ = request.respond(Response::from_string("404 Not Found").with_status_code(404));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fall back to reading a file out of the on-disk target dir, for output that's written there
+/// directly instead of through the in-memory `LiveSite` map (e.g. the `image` Tera function's
+/// processed images, which are cached by content hash rather than re-rendered on every request).
+fn read_target_file(dir: &Path, url: &str) -> Option<(Vec<u8>, String)> {
+    let relative = Path::new(url.trim_start_matches('/'));
+    if relative.components().any(|c| c == std::path::Component::ParentDir) {
+        return None;
+    }
+
+    let path = dir.join(TARGET_SUBDIR).join(relative);
+    let content = fs::read(&path).ok()?;
+    let content_type = mime_guess::from_path(&path)
+        .first_raw()
+        .unwrap_or("application/octet-stream")
+        .to_string();
+    Some((content, content_type))
+}
+
+fn with_trailing_index(url: &str) -> String {
+    if url.ends_with('/') {
+        format!("{}index.html", url)
+    } else {
+        format!("{}/index.html", url)
+    }
+}
+
+#[instrument(skip(site, clients))]
+fn watch_and_rebuild(
+    dir: &Path,
+    drafts: bool,
+    site: &Arc<RwLock<LiveSite>>,
+    clients: &Arc<Mutex<Vec<mpsc::Sender<()>>>>,
+) -> Result<()> {
+    let target_dir = dir.join(TARGET_SUBDIR);
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::watcher(tx, Duration::from_secs(1))?;
+    watcher.watch(dir, RecursiveMode::Recursive)?;
+
     loop {
         match rx.recv() {
             Ok(event) => match event {
@@ -52,7 +376,6 @@ pub fn watch<P: AsRef<Path> + Debug>(dir: P, drafts: bool) -> Result<()> {
                 | DebouncedEvent::Write(path)
                 | DebouncedEvent::Remove(path)
                 | DebouncedEvent::Rename(path, _) => {
-                    // Ignore files in the target dir and temporary files.
                     if !path
                         .extension()
                         .map(|s| s.to_string_lossy().ends_with('~'))
@@ -60,7 +383,13 @@ pub fn watch<P: AsRef<Path> + Debug>(dir: P, drafts: bool) -> Result<()> {
                         && !path.starts_with(&target_dir)
                     {
                         tracing::info!(target:"rebuild", changed=?path);
-                        build(&dir, drafts)?;
+                        match render_to_memory(dir, drafts) {
+                            Ok(rendered) => {
+                                *site.write().unwrap() = rendered;
+                                broadcast_reload(clients);
+                            }
+                            Err(e) => tracing::error!(error=?e, "rebuild failed"),
+                        }
                     }
                 }
                 _ => {}
@@ -70,6 +399,175 @@ pub fn watch<P: AsRef<Path> + Debug>(dir: P, drafts: bool) -> Result<()> {
     }
 }
 
+fn broadcast_reload(clients: &Arc<Mutex<Vec<mpsc::Sender<()>>>>) {
+    let mut clients = clients.lock().unwrap();
+    clients.retain(|tx| tx.send(()).is_ok());
+}
+
+/// A `Read` impl that blocks on a reload channel, yielding one Server-Sent Event per broadcast.
+/// Feeding this to [`Response::new`] with no `data_length` keeps the connection open so the
+/// browser's `EventSource` sees a new `message` event each time the watcher rebuilds the site.
+struct ReloadStream {
+    rx: mpsc::Receiver<()>,
+    pending: Vec<u8>,
+}
+
+impl Read for ReloadStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pending.is_empty() {
+            if self.rx.recv().is_err() {
+                return Ok(0);
+            }
+            self.pending = b"data: reload\n\n".to_vec();
+        }
+        let n = buf.len().min(self.pending.len());
+        buf[..n].copy_from_slice(&self.pending[..n]);
+        self.pending.drain(..n);
+        Ok(n)
+    }
+}
+
+fn handle_reload_conn(request: tiny_http::Request, clients: &Arc<Mutex<Vec<mpsc::Sender<()>>>>) {
+    let (tx, rx) = mpsc::channel();
+    clients.lock().unwrap().push(tx);
+
+    let header = Header::from_bytes(&b"Content-Type"[..], &b"text/event-stream"[..])
+        .expect("valid header");
+    let stream = ReloadStream {
+        rx,
+        pending: Vec::new(),
+    };
+    let response = Response::new(tiny_http::StatusCode(200), vec![header], stream, None, None);
+    let _ = request.respond(response);
+}
+
+/// Render the site in memory: Markdown/LaTeX, SASS, and static assets are held in the returned
+/// `LiveSite` rather than written to the `target` directory. The `sass` and `image` template
+/// functions still write their cached output there, which [`serve`] reads back from disk. Used by
+/// [`serve`] so pages are served from memory instead of forcing a disk round trip for every edit.
+#[instrument]
+fn render_to_memory(dir: &Path, drafts: bool) -> Result<LiveSite> {
+    let config = load_config(dir)?;
+    let content_dir = dir.join(CONTENT_SUBDIR);
+    let mut pages = load_pages(&content_dir)?
+        .into_iter()
+        .filter(|p| drafts || !p.draft)
+        .collect::<Vec<Page>>();
+
+    render_markdown(&mut pages, &config.theme)?;
+
+    let mut site = LiveSite::new();
+    render_html_into_memory(dir, &config, &pages, &mut site)?;
+    render_taxonomies_into_memory(dir, &config, &pages, &mut site)?;
+    render_sass_into_memory(dir, &config.sass, &mut site)?;
+    render_syntax_theme_css_into_memory(&config.theme, &mut site)?;
+    load_assets_into_memory(dir, &mut site)?;
+
+    Ok(site)
+}
+
+#[instrument(skip(config, pages, site))]
+fn render_html_into_memory(
+    dir: &Path,
+    config: &SiteConfig,
+    pages: &[Page],
+    site: &mut LiveSite,
+) -> Result<()> {
+    // Cache sass/image output in the on-disk target dir, which in-memory rebuilds never clean, so
+    // those cache entries persist from one rebuild to the next.
+    let target_dir = dir.join(TARGET_SUBDIR);
+    let templates = build_templates(dir, &target_dir, config)?;
+
+    for page in pages {
+        let url = if page.name == "index" {
+            "/".to_string()
+        } else {
+            format!("/{}/", page.name)
+        };
+
+        let mut context = TeraContext::from_serialize(page)
+            .with_context(|| format!("Error rendering page {}", page.name))?;
+        context.insert("pages", pages);
+
+        let mut rendered = templates
+            .render(&page.template, &context)
+            .with_context(|| format!("Error rendering page {}", page.name))?;
+
+        // Inject the live-reload script just before the closing body tag, if there is one.
+        if let Some(pos) = rendered.rfind("</body>") {
+            rendered.replace_range(pos..pos + "</body>".len(), LIVE_RELOAD_SCRIPT);
+        }
+
+        site.insert(
+            format!("{}index.html", url),
+            MemoryFile {
+                content: rendered.into_bytes(),
+                content_type: "text/html; charset=utf-8",
+            },
+        );
+    }
+
+    Ok(())
+}
+
+#[instrument(skip(site))]
+fn render_sass_into_memory(dir: &Path, sass: &SassConfig, site: &mut LiveSite) -> Result<()> {
+    let sass_dir = dir.join(SASS_SUBDIR);
+
+    let mut options = grass::Options::default().style(if sass.compressed {
+        OutputStyle::Compressed
+    } else {
+        OutputStyle::Expanded
+    });
+
+    for path in sass.load_paths.iter() {
+        options = options.load_path(path);
+    }
+
+    for (output, input) in sass.targets.iter() {
+        let sass_path = sass_dir.join(input);
+        let css = grass::from_path(sass_path.to_string_lossy().as_ref(), &options)
+            .map_err(|e| anyhow::Error::msg(e.to_string()))?;
+
+        site.insert(
+            format!("/css/{}", output.to_string_lossy()),
+            MemoryFile {
+                content: css.into_bytes(),
+                content_type: "text/css",
+            },
+        );
+    }
+
+    Ok(())
+}
+
+#[instrument(skip(site))]
+fn load_assets_into_memory(dir: &Path, site: &mut LiveSite) -> Result<()> {
+    let static_dir = dir.join(STATIC_SUBDIR);
+
+    for entry in WalkDir::new(&static_dir)
+        .into_iter()
+        .filter_map(|r| r.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let rel = entry.path().strip_prefix(&static_dir).unwrap();
+        let content = fs::read(entry.path())
+            .with_context(|| format!("Error reading asset {:?}", entry.path()))?;
+
+        site.insert(
+            format!("/{}", rel.to_string_lossy()),
+            MemoryFile {
+                content,
+                content_type: mime_guess::from_path(rel)
+                    .first_raw()
+                    .unwrap_or("application/octet-stream"),
+            },
+        );
+    }
+
+    Ok(())
+}
+
 #[instrument]
 pub fn build<P: AsRef<Path> + Debug>(dir: P, drafts: bool) -> Result<()> {
     // Convert the path to canonical, if possible.
@@ -101,30 +599,243 @@ pub fn build<P: AsRef<Path> + Debug>(dir: P, drafts: bool) -> Result<()> {
 
     // Copy all asset files.
     // Render SASS files.
+    // Render the syntax highlighting theme stylesheet, if configured.
     // Render HTML files.
     // Render Atom feed.
-    let ((assets, sass), (html, feed)) = rayon::join(
+    // Render taxonomy term pages.
+    let ((assets, (sass, syntax_css)), (html, (feed, taxonomies))) = rayon::join(
         || {
             rayon::join(
                 || copy_assets(&dir, &target_dir),
-                || render_sass(&dir, &target_dir, &config.sass),
+                || {
+                    rayon::join(
+                        || render_sass(&dir, &target_dir, &config.sass),
+                        || render_syntax_theme_css(&target_dir, &config.theme),
+                    )
+                },
             )
         },
         || {
             rayon::join(
-                || render_html(&dir, &target_dir, &pages),
-                || render_feed(&config.title, &target_dir, config.base_url.as_str(), &pages),
+                || render_html(&dir, &target_dir, &config, &pages),
+                || {
+                    rayon::join(
+                        || render_feed(&config.title, &target_dir, config.base_url.as_str(), &pages),
+                        || render_taxonomies(&dir, &target_dir, &config, &pages),
+                    )
+                },
             )
         },
     );
 
     assets
         .and(sass)
+        .and(syntax_css)
         .and(html)
         .and(feed)
+        .and(taxonomies)
         .map(|_| tracing::info!("site built"))
 }
 
+/// The name of the `book.tex` Tera template, and the file it's rendered to in the target dir.
+const BOOK_TEX_FILENAME: &str = "book.tex";
+
+/// The TeX engine invoked on the rendered `book.tex`, if it's on the path.
+const TEX_ENGINE: &str = "xelatex";
+
+#[instrument]
+pub fn build_pdf<P: AsRef<Path> + Debug>(dir: P, drafts: bool) -> Result<()> {
+    // Convert the path to canonical, if possible.
+    let dir = dir
+        .as_ref()
+        .canonicalize()
+        .with_context(|| format!("Failed to find site directory: {:?}", dir))?;
+
+    let config = load_config(&dir)?;
+
+    // Only dated pages belong in the book, same as the Atom feed; concatenate them in date order.
+    let content_dir = dir.join(CONTENT_SUBDIR);
+    let mut pages = load_pages(&content_dir)?
+        .into_iter()
+        .filter(|p| drafts || !p.draft)
+        .filter(|p| p.date.is_some())
+        .collect::<Vec<Page>>();
+    pages.sort_by_key(|p| p.date);
+
+    let target_dir = dir.join(TARGET_SUBDIR);
+    clean_target_dir(&target_dir)?;
+
+    let tex_pages = pages
+        .iter()
+        .map(|page| {
+            Ok(TexPage {
+                title: &page.title,
+                date: page.date,
+                body: render_tex(&page.content)?,
+            })
+        })
+        .collect::<Result<Vec<TexPage>>>()?;
+
+    let templates = Tera::new(
+        dir.join(TEMPLATES_DIR)
+            .join("**")
+            .join("*")
+            .to_string_lossy()
+            .as_ref(),
+    )?;
+    let mut context = TeraContext::new();
+    context.insert("title", &config.title);
+    context.insert("pages", &tex_pages);
+
+    let tex_path = target_dir.join(BOOK_TEX_FILENAME);
+    let f = BufWriter::new(
+        File::create(&tex_path).with_context(|| format!("Error creating {:?}", &tex_path))?,
+    );
+    templates
+        .render_to(BOOK_TEX_FILENAME, &context, f)
+        .context("Error rendering book.tex")?;
+
+    match invoke_tex_engine(&target_dir) {
+        Ok(()) => tracing::info!("book rendered to PDF"),
+        Err(e) => tracing::warn!(
+            error=?e,
+            "unable to run {} to produce a PDF; book.tex was still written",
+            TEX_ENGINE
+        ),
+    }
+
+    Ok(())
+}
+
+/// One page's Markdown rendered to a LaTeX fragment, ready to be dropped into the `book.tex`
+/// template.
+#[derive(Debug, Serialize)]
+struct TexPage<'a> {
+    title: &'a str,
+    date: Option<DateTime<Utc>>,
+    body: String,
+}
+
+/// Map a page's Markdown events to LaTeX commands: headings become `\section`/`\subsection`/etc,
+/// fenced code becomes an `lstlisting`, and the math already present in the source (inline
+/// `$...$` or a fenced ` ```latex ` block) passes through verbatim, since it's already TeX.
+fn render_tex(content: &str) -> Result<String> {
+    let md_opts = Options::all();
+    let mut out = String::with_capacity(content.len() * 2);
+    let mut fence_kind: Option<String> = None;
+    let mut skip_table = false;
+
+    for event in Parser::new_ext(content, md_opts) {
+        // Tables aren't mapped to a LaTeX environment; skip the whole block rather than leaking
+        // unstructured cell/row text into the document.
+        if skip_table {
+            if matches!(event, Event::End(Tag::Table(_))) {
+                skip_table = false;
+            }
+            continue;
+        }
+
+        match event {
+            Event::Start(Tag::Table(_)) => {
+                tracing::warn!("tables aren't supported in the PDF backend; skipping");
+                skip_table = true;
+            }
+            Event::Start(Tag::Heading(level, ..)) => out.push_str(match heading_level_num(level) {
+                1 => "\\section{",
+                2 => "\\subsection{",
+                3 => "\\subsubsection{",
+                4 => "\\paragraph{",
+                _ => "\\subparagraph{",
+            }),
+            Event::End(Tag::Heading(..)) => out.push_str("}\n"),
+            Event::End(Tag::Paragraph) => out.push_str("\n\n"),
+            Event::Start(Tag::Emphasis) => out.push_str("\\emph{"),
+            Event::End(Tag::Emphasis) => out.push('}'),
+            Event::Start(Tag::Strong) => out.push_str("\\textbf{"),
+            Event::End(Tag::Strong) => out.push('}'),
+            Event::Start(Tag::List(None)) => out.push_str("\\begin{itemize}\n"),
+            Event::End(Tag::List(None)) => out.push_str("\\end{itemize}\n"),
+            Event::Start(Tag::List(Some(_))) => out.push_str("\\begin{enumerate}\n"),
+            Event::End(Tag::List(Some(_))) => out.push_str("\\end{enumerate}\n"),
+            Event::Start(Tag::Item) => out.push_str("\\item "),
+            Event::End(Tag::Item) => out.push('\n'),
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(kind))) => {
+                let is_latex = kind.as_ref() == "latex";
+                fence_kind = Some(kind.to_string());
+                if !is_latex {
+                    out.push_str("\\begin{lstlisting}\n");
+                }
+            }
+            Event::End(Tag::CodeBlock(CodeBlockKind::Fenced(_))) => {
+                if fence_kind.as_deref() != Some("latex") {
+                    out.push_str("\\end{lstlisting}\n");
+                }
+                fence_kind = None;
+            }
+            Event::Code(s) => {
+                if s.starts_with('$') && s.ends_with('$') {
+                    // Already TeX math; pass it through verbatim.
+                    out.push_str(&s);
+                } else {
+                    out.push_str(&format!("\\texttt{{{}}}", escape_tex(&s)));
+                }
+            }
+            Event::Text(s) => {
+                if fence_kind.is_some() {
+                    // Inside a fenced code or latex block; pass the raw source through.
+                    out.push_str(&s);
+                } else {
+                    out.push_str(&escape_tex(&s));
+                }
+            }
+            Event::SoftBreak => out.push(' '),
+            Event::HardBreak => out.push_str("\\\\\n"),
+            _ => {}
+        }
+    }
+
+    Ok(out)
+}
+
+/// Escape LaTeX's special characters in plain text (outside math and code).
+fn escape_tex(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '\\' => out.push_str("\\textbackslash{}"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Compile the rendered `book.tex` to a PDF with `TEX_ENGINE` (`xelatex`), if it's on the path;
+/// no other TeX engine is tried. This is
+/// best-effort: a missing or failing TeX engine doesn't fail the build, since `book.tex` is
+/// useful (and editable) on its own.
+#[instrument]
+fn invoke_tex_engine(target_dir: &Path) -> Result<()> {
+    let status = Command::new(TEX_ENGINE)
+        .arg("-interaction=nonstopmode")
+        .arg("-output-directory")
+        .arg(target_dir)
+        .arg(target_dir.join(BOOK_TEX_FILENAME))
+        .status()
+        .with_context(|| format!("Unable to run {}", TEX_ENGINE))?;
+
+    if !status.success() {
+        bail!("{} exited with {}", TEX_ENGINE, status);
+    }
+
+    Ok(())
+}
+
 #[instrument]
 fn load_config(dir: &Path) -> Result<SiteConfig> {
     let config_path = dir.join(CONFIG_FILENAME);
@@ -222,7 +933,9 @@ fn copy_assets(dir: &Path, target_dir: &Path) -> Result<()> {
 fn render_sass(dir: &Path, target_dir: &Path, sass: &SassConfig) -> Result<()> {
     let sass_dir = dir.join(SASS_SUBDIR);
     let css_dir = target_dir.join(CSS_SUBDIR);
-    fs::create_dir(&css_dir).with_context(|| format!("Error creating {:?}", &css_dir))?;
+    // Use `create_dir_all` rather than `create_dir` so this is safe to re-run for a single
+    // incremental SASS rebuild, where the directory already exists from the initial build.
+    fs::create_dir_all(&css_dir).with_context(|| format!("Error creating {:?}", &css_dir))?;
 
     let mut options = grass::Options::default().style(if sass.compressed {
         OutputStyle::Compressed
@@ -246,31 +959,157 @@ fn render_sass(dir: &Path, target_dir: &Path, sass: &SassConfig) -> Result<()> {
     })
 }
 
+/// The sentinel `theme` value that switches syntax highlighting from inline `style="..."`
+/// attributes to `<span class="...">` tokens, so highlighting can be restyled (or dark-mode
+/// switched) from CSS instead of being baked into every page.
+const CSS_HIGHLIGHT_THEME: &str = "css";
+
+/// A light and dark pair of bundled Syntect themes used to generate `syntax-theme.css` when
+/// `theme = "css"` is configured.
+const CSS_HIGHLIGHT_LIGHT_THEME: &str = "InspiredGitHub";
+const CSS_HIGHLIGHT_DARK_THEME: &str = "base16-ocean.dark";
+const SYNTAX_THEME_FILENAME: &str = "syntax-theme.css";
+
+/// Which syntax highlighting strategy `render_markdown` should use for a fenced code block.
+enum Highlight<'a> {
+    /// Bake the given theme's colors directly into `style="..."` attributes.
+    Inline(&'a syntect::highlighting::Theme),
+    /// Emit `<span class="...">` tokens styled by the generated `syntax-theme.css`.
+    Classed,
+}
+
+/// Generate `syntax-theme.css`'s contents from a light/dark pair of bundled themes, or `None` if
+/// the configured `theme` isn't the `"css"` sentinel.
+fn syntax_theme_css(theme: &str) -> Result<Option<String>> {
+    if theme != CSS_HIGHLIGHT_THEME {
+        return Ok(None);
+    }
+
+    let light = THEME_SET
+        .themes
+        .get(CSS_HIGHLIGHT_LIGHT_THEME)
+        .ok_or_else(|| anyhow!("Invalid syntax theme: {:?}", CSS_HIGHLIGHT_LIGHT_THEME))?;
+    let dark = THEME_SET
+        .themes
+        .get(CSS_HIGHLIGHT_DARK_THEME)
+        .ok_or_else(|| anyhow!("Invalid syntax theme: {:?}", CSS_HIGHLIGHT_DARK_THEME))?;
+
+    let mut css = css_for_theme_with_class_style(light, ClassStyle::Spaced)
+        .with_context(|| format!("Error generating CSS for theme {:?}", CSS_HIGHLIGHT_LIGHT_THEME))?;
+    css.push_str("\n@media (prefers-color-scheme: dark) {\n");
+    css.push_str(
+        &css_for_theme_with_class_style(dark, ClassStyle::Spaced)
+            .with_context(|| format!("Error generating CSS for theme {:?}", CSS_HIGHLIGHT_DARK_THEME))?,
+    );
+    css.push_str("\n}\n");
+
+    Ok(Some(css))
+}
+
+/// Write `syntax-theme.css` to the target dir when the configured `theme` is the `"css"`
+/// sentinel; otherwise a no-op.
+#[instrument]
+fn render_syntax_theme_css(target_dir: &Path, theme: &str) -> Result<()> {
+    let css = match syntax_theme_css(theme)? {
+        Some(css) => css,
+        None => return Ok(()),
+    };
+
+    let css_dir = target_dir.join(CSS_SUBDIR);
+    fs::create_dir_all(&css_dir).with_context(|| format!("Error creating {:?}", &css_dir))?;
+
+    let path = css_dir.join(SYNTAX_THEME_FILENAME);
+    fs::write(&path, css).with_context(|| format!("Error writing {:?}", &path))
+}
+
+/// As `render_syntax_theme_css`, but inserted into an in-memory `LiveSite` for [`serve`].
+#[instrument(skip(site))]
+fn render_syntax_theme_css_into_memory(theme: &str, site: &mut LiveSite) -> Result<()> {
+    if let Some(css) = syntax_theme_css(theme)? {
+        site.insert(
+            format!("/{}/{}", CSS_SUBDIR, SYNTAX_THEME_FILENAME),
+            MemoryFile {
+                content: css.into_bytes(),
+                content_type: "text/css",
+            },
+        );
+    }
+    Ok(())
+}
+
 #[instrument(skip(pages))]
 fn render_markdown(pages: &mut [Page], theme: &str) -> Result<()> {
     let md_opts = Options::all();
-    let theme = THEME_SET
-        .themes
-        .get(theme)
-        .ok_or_else(|| anyhow!("Invalid syntax theme: {:?}", theme))?;
-
-    let inline_opts = Opts::builder().display_mode(false).build()?;
-    let block_opts = Opts::builder().display_mode(true).build()?;
+    let highlight = if theme == CSS_HIGHLIGHT_THEME {
+        Highlight::Classed
+    } else {
+        Highlight::Inline(
+            THEME_SET
+                .themes
+                .get(theme)
+                .ok_or_else(|| anyhow!("Invalid syntax theme: {:?}", theme))?,
+        )
+    };
 
     pages.iter_mut().try_for_each(|page| {
         tracing::debug!(page=?page.name, "parsing markdown");
         let mut out = String::with_capacity(page.content.len() * 2);
         let mut fence_kind: Option<String> = None;
         let mut events = Vec::with_capacity(1024);
+        let mut equation_errors = Vec::new();
+
+        // Heading anchor/table-of-contents state. `heading_start_idx` points at the placeholder
+        // `Event::Start(Tag::Heading(..))` already pushed to `events`, which gets rewritten with
+        // an `id` once the heading's text (and thus its slug) is fully known at the matching
+        // `Event::End`.
+        let mut heading_start_idx: Option<usize> = None;
+        let mut heading_text = String::new();
+        let mut seen_slugs: HashMap<String, u32> = HashMap::new();
+        let mut toc_flat: Vec<(u8, String, String)> = Vec::new();
+
         for event in Parser::new_ext(&page.content, md_opts) {
             match &event {
+                Event::Start(Tag::Heading(level, ..)) => {
+                    heading_start_idx = Some(events.len());
+                    heading_text.clear();
+                    events.push(event);
+                }
+                Event::End(Tag::Heading(level, ..)) => {
+                    if let Some(start_idx) = heading_start_idx.take() {
+                        let n = heading_level_num(*level);
+                        let mut slug = slugify(&heading_text);
+                        if slug.is_empty() {
+                            slug = format!("heading-{}", toc_flat.len() + 1);
+                        }
+                        let count = seen_slugs.entry(slug.clone()).or_insert(0);
+                        if *count > 0 {
+                            slug = format!("{}-{}", slug, count);
+                        }
+                        *count += 1;
+
+                        events[start_idx] = Event::Html(format!(r#"<h{} id="{}">"#, n, slug).into());
+                        events.push(Event::Html(
+                            format!(
+                                r#"<a href="#{}" class="anchor" aria-hidden="true">#</a></h{}>"#,
+                                slug, n
+                            )
+                            .into(),
+                        ));
+                        toc_flat.push((n, heading_text.clone(), slug));
+                    } else {
+                        events.push(event);
+                    }
+                }
                 Event::Code(s) => {
+                    if heading_start_idx.is_some() {
+                        heading_text.push_str(s);
+                    }
                     if s.starts_with('$') && s.ends_with('$') {
                         // Convert inline LaTeX blocks (e.g. `$N+1`) to HTML.
                         let s = &s[1..s.len() - 1];
                         tracing::debug!(block=?s, "rendering inline equation");
                         events.push(Event::Html(
-                            katex::render_with_opts(s, &inline_opts)?.into(),
+                            render_equation(s, false, &mut equation_errors)?.into(),
                         ));
                     } else {
                         // Pass regular inline code blocks on to the formatter.
@@ -304,13 +1143,28 @@ fn render_markdown(pages: &mut [Page], theme: &str) -> Result<()> {
                         if kind.as_str() == "latex" {
                             // Render LaTeX as HTML using KaTeX.
                             tracing::debug!(block=?s, "rendering display equation");
-                            let html = katex::render_with_opts(s, &block_opts)?;
+                            let html = render_equation(s, true, &mut equation_errors)?;
                             events.push(Event::Html(html.into()))
                         } else if let Some(syntax) = SYNTAX_SET.find_syntax_by_token(kind) {
                             // If we can find a Syntect syntax for the given kind, format it
                             // as syntax highlighted HTML.
                             tracing::debug!(kind=?kind, block=?s, "rendering code block");
-                            let html = highlighted_html_for_string(s, &SYNTAX_SET, syntax, theme);
+                            let html = match &highlight {
+                                Highlight::Inline(theme) => {
+                                    highlighted_html_for_string(s, &SYNTAX_SET, syntax, theme)
+                                }
+                                Highlight::Classed => {
+                                    let mut generator = ClassedHTMLGenerator::new_with_class_style(
+                                        syntax,
+                                        &SYNTAX_SET,
+                                        ClassStyle::Spaced,
+                                    );
+                                    for line in LinesWithEndings::from(s) {
+                                        generator.parse_html_for_line(line);
+                                    }
+                                    format!("<pre><code>{}</code></pre>", generator.finalize())
+                                }
+                            };
                             events.push(Event::Html(html.into()))
                         } else {
                             // If we don't know what kind this code is, just slap it in a
@@ -324,6 +1178,9 @@ fn render_markdown(pages: &mut [Page], theme: &str) -> Result<()> {
                         }
                     } else {
                         // If we're not in a fenced code block, just pass the text on.
+                        if heading_start_idx.is_some() {
+                            heading_text.push_str(s);
+                        }
                         events.push(event);
                     }
                 }
@@ -335,44 +1192,158 @@ fn render_markdown(pages: &mut [Page], theme: &str) -> Result<()> {
         // Render as HTML.
         html::push_html(&mut out, events.into_iter());
         page.content = out;
+        page.toc = build_toc(toc_flat);
+
+        for error in &equation_errors {
+            tracing::warn!(page=?page.name, %error, "invalid LaTeX equation, rendered as a placeholder");
+        }
 
         Ok(())
     })
 }
 
+/// Render one equation's source (already stripped of its Markdown delimiter) as KaTeX HTML via
+/// `latex::render_all`, recovering from a bad equation rather than failing the whole page: a
+/// rejected equation becomes an inline error placeholder, and its `EquationError` is appended to
+/// `errors` so the caller can log it once the page is done.
+fn render_equation(
+    source: &str,
+    display: bool,
+    errors: &mut Vec<latex::EquationError>,
+) -> Result<String> {
+    let wrapped = if display {
+        format!("$${source}$$")
+    } else {
+        format!(r#"\\({source}\\)"#)
+    };
+    let (html, mut page_errors) = latex::render_all(&wrapped, &HashMap::new(), &DEFAULT_SYMBOLS)
+        .with_context(|| format!("Invalid LaTeX delimiters in {:?}", source))?;
+    errors.append(&mut page_errors);
+    Ok(html)
+}
+
+/// Map a `pulldown_cmark::HeadingLevel` to its numeric heading level (`H1` to `1`, etc.)
+fn heading_level_num(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+/// One entry in a page's table of contents, nested so that a heading following a shallower one
+/// becomes its child.
+#[derive(Debug, Default, Clone, Serialize)]
+struct TocEntry {
+    level: u8,
+    title: String,
+    slug: String,
+    children: Vec<TocEntry>,
+}
+
+/// Build a nested table of contents from a flat, document-order list of `(level, title, slug)`
+/// headings, by maintaining a stack keyed on heading level: an entry is closed out (and attached
+/// to its parent, or the root if none) as soon as a same-or-shallower heading is seen.
+fn build_toc(flat: Vec<(u8, String, String)>) -> Vec<TocEntry> {
+    let mut stack: Vec<(u8, TocEntry)> = Vec::new();
+    let mut roots: Vec<TocEntry> = Vec::new();
+
+    fn close_top(stack: &mut Vec<(u8, TocEntry)>, roots: &mut Vec<TocEntry>) {
+        if let Some((_, entry)) = stack.pop() {
+            match stack.last_mut() {
+                Some((_, parent)) => parent.children.push(entry),
+                None => roots.push(entry),
+            }
+        }
+    }
+
+    for (level, title, slug) in flat {
+        while matches!(stack.last(), Some((top_level, _)) if *top_level >= level) {
+            close_top(&mut stack, &mut roots);
+        }
+        stack.push((
+            level,
+            TocEntry {
+                level,
+                title,
+                slug,
+                children: Vec::new(),
+            },
+        ));
+    }
+    while !stack.is_empty() {
+        close_top(&mut stack, &mut roots);
+    }
+
+    roots
+}
+
 #[instrument(skip(pages))]
-fn render_html(dir: &Path, target_dir: &Path, pages: &[Page]) -> Result<()> {
-    let templates = Tera::new(
+fn render_html(dir: &Path, target_dir: &Path, config: &SiteConfig, pages: &[Page]) -> Result<()> {
+    let templates = build_templates(dir, target_dir, config)?;
+    pages
+        .iter()
+        .try_for_each(|page| render_page_html(&templates, target_dir, page, pages))
+}
+
+/// Build the Tera template engine, registering the `sass` and `image` functions so templates can
+/// compile SASS and process images on demand, caching the results in the target directory.
+fn build_templates(dir: &Path, target_dir: &Path, config: &SiteConfig) -> Result<Tera> {
+    let mut templates = Tera::new(
         dir.join(TEMPLATES_DIR)
             .join("**")
             .join("*")
             .to_string_lossy()
             .as_ref(),
     )?;
-    pages.iter().try_for_each(|page| {
-        let path = if page.name == "index" {
-            target_dir.join(INDEX_FILENAME)
-        } else {
-            target_dir.join(&page.name).join(INDEX_FILENAME)
-        };
+    templates.register_function(
+        "sass",
+        SassContext {
+            sass_dir: dir.join(SASS_SUBDIR),
+            output_dir: target_dir.to_path_buf(),
+            base_url: config.base_url.clone(),
+        },
+    );
+    templates.register_function(
+        "image",
+        ImageContext {
+            content_dir: dir.join(CONTENT_SUBDIR),
+            static_dir: dir.join(STATIC_SUBDIR),
+            output_dir: target_dir.to_path_buf(),
+            base_url: config.base_url.clone(),
+        },
+    );
+    Ok(templates)
+}
 
-        if let Some(parent) = path.parent() {
-            let _ = fs::create_dir(parent);
-        }
+/// Render a single page's HTML, shared by the full `render_html` pass and incremental content
+/// rebuilds in `watch`.
+#[instrument(skip(templates, page, pages))]
+fn render_page_html(templates: &Tera, target_dir: &Path, page: &Page, pages: &[Page]) -> Result<()> {
+    let path = if page.name == "index" {
+        target_dir.join(INDEX_FILENAME)
+    } else {
+        target_dir.join(&page.name).join(INDEX_FILENAME)
+    };
 
-        let f = BufWriter::new(
-            File::create(&path).with_context(|| format!("Error creating {:?}", &path))?,
-        );
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("Error creating {:?}", parent))?;
+    }
 
-        let mut context = TeraContext::from_serialize(page)
-            .with_context(|| format!("Error rendering page {}", page.name))?;
-        context.insert("pages", pages);
+    let f =
+        BufWriter::new(File::create(&path).with_context(|| format!("Error creating {:?}", &path))?);
 
-        tracing::debug!(page=?page.name, dst=?path, "rendered html");
-        templates
-            .render_to(&page.template, &context, f)
-            .with_context(|| format!("Error rendering page {}", page.name))
-    })
+    let mut context = TeraContext::from_serialize(page)
+        .with_context(|| format!("Error rendering page {}", page.name))?;
+    context.insert("pages", pages);
+
+    tracing::debug!(page=?page.name, dst=?path, "rendered html");
+    templates
+        .render_to(&page.template, &context, f)
+        .with_context(|| format!("Error rendering page {}", page.name))
 }
 
 #[instrument(skip(pages))]
@@ -410,6 +1381,208 @@ fn render_feed(title: &str, target_dir: &Path, base_url: &str, pages: &[Page]) -
     Ok(())
 }
 
+/// Render every configured taxonomy's term pages and term index, grouping pages by the terms
+/// named in their front matter.
+#[instrument(skip(config, pages))]
+fn render_taxonomies(
+    dir: &Path,
+    target_dir: &Path,
+    config: &SiteConfig,
+    pages: &[Page],
+) -> Result<()> {
+    if config.taxonomies.is_empty() {
+        return Ok(());
+    }
+
+    let templates = build_templates(dir, target_dir, config)?;
+
+    config
+        .taxonomies
+        .iter()
+        .try_for_each(|taxonomy| render_taxonomy(&templates, target_dir, taxonomy, pages))
+}
+
+#[instrument(skip(templates, pages))]
+fn render_taxonomy(
+    templates: &Tera,
+    target_dir: &Path,
+    taxonomy: &TaxonomyConfig,
+    pages: &[Page],
+) -> Result<()> {
+    let mut terms: HashMap<&str, Vec<&Page>> = HashMap::new();
+    for page in pages {
+        if let Some(values) = page.taxonomies.get(&taxonomy.name) {
+            for term in values {
+                terms.entry(term.as_str()).or_default().push(page);
+            }
+        }
+    }
+
+    let taxonomy_dir = target_dir.join(&taxonomy.name);
+    fs::create_dir_all(&taxonomy_dir)
+        .with_context(|| format!("Error creating {:?}", &taxonomy_dir))?;
+
+    let mut term_list: Vec<Term> = terms
+        .iter()
+        .map(|(name, pages)| Term {
+            name: name.to_string(),
+            slug: slugify(name),
+            count: pages.len(),
+        })
+        .collect();
+    term_list.sort_by(|a, b| a.name.cmp(&b.name));
+
+    tracing::debug!(taxonomy=?taxonomy.name, terms=term_list.len(), "rendering taxonomy index");
+    let mut index_context = TeraContext::new();
+    index_context.insert("taxonomy", &taxonomy.name);
+    index_context.insert("terms", &term_list);
+    let index_path = taxonomy_dir.join(INDEX_FILENAME);
+    let f = BufWriter::new(
+        File::create(&index_path).with_context(|| format!("Error creating {:?}", &index_path))?,
+    );
+    templates
+        .render_to(&taxonomy.index_template, &index_context, f)
+        .with_context(|| format!("Error rendering taxonomy index {}", taxonomy.name))?;
+
+    terms.into_iter().try_for_each(|(term, mut term_pages)| {
+        term_pages.sort_by_key(|p| std::cmp::Reverse(p.date));
+
+        let slug = slugify(term);
+        let term_dir = taxonomy_dir.join(&slug);
+        fs::create_dir_all(&term_dir)
+            .with_context(|| format!("Error creating {:?}", &term_dir))?;
+
+        tracing::debug!(taxonomy=?taxonomy.name, term=?term, "rendering taxonomy term");
+        let mut context = TeraContext::new();
+        context.insert("taxonomy", &taxonomy.name);
+        context.insert("term", term);
+        context.insert("pages", &term_pages);
+
+        let path = term_dir.join(INDEX_FILENAME);
+        let f =
+            BufWriter::new(File::create(&path).with_context(|| format!("Error creating {:?}", &path))?);
+        templates
+            .render_to(&taxonomy.template, &context, f)
+            .with_context(|| format!("Error rendering taxonomy term {}/{}", taxonomy.name, term))
+    })
+}
+
+/// As `render_taxonomies`, but inserted into an in-memory `LiveSite` for [`serve`].
+#[instrument(skip(config, pages, site))]
+fn render_taxonomies_into_memory(
+    dir: &Path,
+    config: &SiteConfig,
+    pages: &[Page],
+    site: &mut LiveSite,
+) -> Result<()> {
+    if config.taxonomies.is_empty() {
+        return Ok(());
+    }
+
+    let target_dir = dir.join(TARGET_SUBDIR);
+    let templates = build_templates(dir, &target_dir, config)?;
+
+    config
+        .taxonomies
+        .iter()
+        .try_for_each(|taxonomy| render_taxonomy_into_memory(&templates, taxonomy, pages, site))
+}
+
+#[instrument(skip(templates, pages, site))]
+fn render_taxonomy_into_memory(
+    templates: &Tera,
+    taxonomy: &TaxonomyConfig,
+    pages: &[Page],
+    site: &mut LiveSite,
+) -> Result<()> {
+    let mut terms: HashMap<&str, Vec<&Page>> = HashMap::new();
+    for page in pages {
+        if let Some(values) = page.taxonomies.get(&taxonomy.name) {
+            for term in values {
+                terms.entry(term.as_str()).or_default().push(page);
+            }
+        }
+    }
+
+    let mut term_list: Vec<Term> = terms
+        .iter()
+        .map(|(name, pages)| Term {
+            name: name.to_string(),
+            slug: slugify(name),
+            count: pages.len(),
+        })
+        .collect();
+    term_list.sort_by(|a, b| a.name.cmp(&b.name));
+
+    tracing::debug!(taxonomy=?taxonomy.name, terms=term_list.len(), "rendering taxonomy index");
+    let mut index_context = TeraContext::new();
+    index_context.insert("taxonomy", &taxonomy.name);
+    index_context.insert("terms", &term_list);
+    let index_html = templates
+        .render(&taxonomy.index_template, &index_context)
+        .with_context(|| format!("Error rendering taxonomy index {}", taxonomy.name))?;
+    site.insert(
+        format!("/{}/index.html", taxonomy.name),
+        MemoryFile {
+            content: index_html.into_bytes(),
+            content_type: "text/html; charset=utf-8",
+        },
+    );
+
+    terms.into_iter().try_for_each(|(term, mut term_pages)| {
+        term_pages.sort_by_key(|p| std::cmp::Reverse(p.date));
+
+        let slug = slugify(term);
+
+        tracing::debug!(taxonomy=?taxonomy.name, term=?term, "rendering taxonomy term");
+        let mut context = TeraContext::new();
+        context.insert("taxonomy", &taxonomy.name);
+        context.insert("term", term);
+        context.insert("pages", &term_pages);
+
+        let html = templates
+            .render(&taxonomy.template, &context)
+            .with_context(|| format!("Error rendering taxonomy term {}/{}", taxonomy.name, term))?;
+        site.insert(
+            format!("/{}/{}/index.html", taxonomy.name, slug),
+            MemoryFile {
+                content: html.into_bytes(),
+                content_type: "text/html; charset=utf-8",
+            },
+        );
+        Ok(())
+    })
+}
+
+/// A term summary exposed to taxonomy index templates.
+#[derive(Debug, Serialize)]
+struct Term {
+    name: String,
+    slug: String,
+    count: usize,
+}
+
+/// Turn a taxonomy term or heading into a URL-safe path segment: ASCII letters and digits are kept
+/// and lowercased, every run of other characters (non-ASCII letters included) becomes a single
+/// hyphen, and leading or trailing hyphens are dropped.
+fn slugify(s: &str) -> String {
+    let mut slug = String::with_capacity(s.len());
+    let mut last_was_hyphen = true;
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+            last_was_hyphen = false;
+        } else if !last_was_hyphen {
+            slug.push('-');
+            last_was_hyphen = true;
+        }
+    }
+    if slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct SiteConfig {
@@ -421,6 +1594,9 @@ struct SiteConfig {
 
     #[serde(default, skip_serializing)]
     sass: SassConfig,
+
+    #[serde(default, skip_serializing)]
+    taxonomies: Vec<TaxonomyConfig>,
 }
 
 fn default_theme() -> String {
@@ -435,6 +1611,16 @@ struct SassConfig {
     load_paths: Vec<PathBuf>,
 }
 
+/// A named taxonomy (e.g. `tags`, `categories`), with the templates used to render its term
+/// pages and the index of all its terms.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct TaxonomyConfig {
+    name: String,
+    template: String,
+    index_template: String,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct Page {
     title: String,
@@ -445,6 +1631,9 @@ struct Page {
     #[serde(default)]
     draft: bool,
 
+    #[serde(default)]
+    taxonomies: HashMap<String, Vec<String>>,
+
     #[serde(skip_deserializing)]
     excerpt: Option<String>,
 
@@ -453,6 +1642,9 @@ struct Page {
 
     #[serde(skip_deserializing)]
     content: String,
+
+    #[serde(skip_deserializing, default)]
+    toc: Vec<TocEntry>,
 }
 
 const CONTENT_SUBDIR: &str = "content";
@@ -469,6 +1661,7 @@ const INDEX_FILENAME: &str = "index.html";
 lazy_static! {
     static ref SYNTAX_SET: SyntaxSet = SyntaxSet::load_defaults_newlines();
     static ref THEME_SET: ThemeSet = ThemeSet::load_defaults();
+    static ref DEFAULT_SYMBOLS: latex::SymbolTable = latex::default_symbols();
 }
 
 #[ctor]